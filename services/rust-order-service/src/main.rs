@@ -1,19 +1,34 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Extension, Path, Query, State},
+    http::{header, HeaderName, HeaderValue, Method, Request, StatusCode},
     response::{IntoResponse, Response},
-    routing::{get, post},
+    routing::{get, patch, post},
     Json, Router,
 };
 use mongodb::{
     bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
     Client, Collection, Database,
 };
-use prometheus::{Encoder, IntCounter, Histogram, TextEncoder, register_int_counter, register_histogram};
+use prometheus::{Encoder, Gauge, IntCounter, IntCounterVec, Histogram, TextEncoder, register_gauge, register_int_counter, register_int_counter_vec, register_histogram};
 use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, sync::Arc};
-use tower_http::trace::TraceLayer;
-use tracing::{info, warn, error, instrument};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+use pin_project::{pin_project, pinned_drop};
+use tower::{Layer, Service};
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{Any, CorsLayer},
+    propagate_header::PropagateHeaderLayer,
+    sensitive_headers::SetSensitiveHeadersLayer,
+    trace::TraceLayer,
+};
+use tracing::{info, warn, error, instrument, Instrument};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use chrono::{DateTime, Utc};
@@ -23,6 +38,7 @@ use opentelemetry::trace::TraceContextExt;
 use opentelemetry::global;
 use opentelemetry::propagation::Injector;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
+use uuid::Uuid;
 
 // Application state
 #[derive(Clone)]
@@ -53,7 +69,12 @@ struct Metrics {
     requests_total: IntCounter,
     orders_created: IntCounter,
     orders_queried: IntCounter,
+    orders_status_changed: IntCounterVec,
     request_duration: Histogram,
+    outbox_pending: Gauge,
+    returns_created: IntCounter,
+    returns_refunded: IntCounter,
+    low_stock_alerts_total: IntCounter,
 }
 
 impl Metrics {
@@ -62,7 +83,214 @@ impl Metrics {
             requests_total: register_int_counter!("http_requests_total", "Total HTTP requests").unwrap(),
             orders_created: register_int_counter!("orders_created_total", "Total orders created").unwrap(),
             orders_queried: register_int_counter!("orders_queried_total", "Total order queries").unwrap(),
+            orders_status_changed: register_int_counter_vec!(
+                "orders_status_changed_total",
+                "Total order status transitions, labeled by target status",
+                &["status"]
+            ).unwrap(),
             request_duration: register_histogram!("http_request_duration_seconds", "HTTP request duration").unwrap(),
+            outbox_pending: register_gauge!("outbox_pending", "Number of unpublished events in the outbox").unwrap(),
+            returns_created: register_int_counter!("returns_created_total", "Total return requests created").unwrap(),
+            returns_refunded: register_int_counter!("returns_refunded_total", "Total returns refunded").unwrap(),
+            low_stock_alerts_total: register_int_counter!("low_stock_alerts_total", "Total inventory.low_stock events published").unwrap(),
+        }
+    }
+}
+
+// Header carrying the correlation id, both inbound and to downstream services
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Correlation id for a single inbound request, threaded through handlers via request extensions
+// and propagated to downstream calls and NATS events alongside the OpenTelemetry trace id.
+#[derive(Debug, Clone, Copy)]
+struct RequestId(Uuid);
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// tower Layer that assigns a request id (honoring an inbound X-Request-Id when it parses as a
+// UUID; any other inbound value is replaced by a fresh one) and access-logs
+// method/path/status/latency on completion, via a pinned-drop guard so aborted or panicked
+// requests are logged too.
+#[derive(Debug, Clone, Copy, Default)]
+struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestIdService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = RequestIdFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| Uuid::parse_str(v).ok())
+            .unwrap_or_else(Uuid::new_v4);
+
+        req.extensions_mut().insert(RequestId(request_id));
+        if let Ok(val) = HeaderValue::from_str(&request_id.to_string()) {
+            req.headers_mut().insert(REQUEST_ID_HEADER, val);
+        }
+
+        let client_addr = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr);
+
+        let guard = AccessLogGuard {
+            request_id,
+            method: req.method().clone(),
+            path: req.uri().path().to_string(),
+            client_addr,
+            start: Instant::now(),
+            status: None,
+        };
+
+        let span = tracing::info_span!("request", request_id = %request_id);
+        let inner = self.inner.call(req).instrument(span);
+
+        RequestIdFuture {
+            inner,
+            guard: Some(guard),
+        }
+    }
+}
+
+// Captures the fields of the access-log line; logged exactly once, either when the wrapped
+// future resolves or (if it never does) when it is dropped.
+struct AccessLogGuard {
+    request_id: Uuid,
+    method: Method,
+    path: String,
+    client_addr: Option<SocketAddr>,
+    start: Instant,
+    status: Option<StatusCode>,
+}
+
+impl AccessLogGuard {
+    fn log(&self) {
+        let latency_ms = self.start.elapsed().as_millis();
+        match self.status {
+            Some(status) => info!(
+                request_id = %self.request_id,
+                method = %self.method,
+                path = %self.path,
+                client_addr = ?self.client_addr,
+                status = status.as_u16(),
+                latency_ms,
+                "request completed"
+            ),
+            None => warn!(
+                request_id = %self.request_id,
+                method = %self.method,
+                path = %self.path,
+                client_addr = ?self.client_addr,
+                latency_ms,
+                "request aborted before completion"
+            ),
+        }
+    }
+}
+
+#[pin_project(PinnedDrop)]
+struct RequestIdFuture<F> {
+    #[pin]
+    inner: tracing::instrument::Instrumented<F>,
+    guard: Option<AccessLogGuard>,
+}
+
+impl<F, ResBody, E> Future for RequestIdFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = Result<Response<ResBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = futures::ready!(this.inner.poll(cx));
+
+        if let Some(mut guard) = this.guard.take() {
+            if let Ok(response) = &result {
+                guard.status = Some(response.status());
+            }
+            guard.log();
+        }
+
+        Poll::Ready(result)
+    }
+}
+
+#[pinned_drop]
+impl<F> PinnedDrop for RequestIdFuture<F> {
+    fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        if let Some(guard) = this.guard.take() {
+            guard.log();
+        }
+    }
+}
+
+// Order lifecycle status
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum OrderStatus {
+    Pending,
+    Confirmed,
+    Shipped,
+    Delivered,
+    Cancelled,
+    Returned,
+}
+
+impl OrderStatus {
+    // Allowed target statuses for each source status
+    fn allowed_transitions(self) -> &'static [OrderStatus] {
+        match self {
+            OrderStatus::Pending => &[OrderStatus::Confirmed, OrderStatus::Cancelled],
+            OrderStatus::Confirmed => &[OrderStatus::Shipped, OrderStatus::Cancelled],
+            OrderStatus::Shipped => &[OrderStatus::Delivered],
+            OrderStatus::Delivered => &[OrderStatus::Returned],
+            OrderStatus::Cancelled => &[],
+            OrderStatus::Returned => &[],
+        }
+    }
+
+    fn can_transition_to(self, target: OrderStatus) -> bool {
+        self.allowed_transitions().contains(&target)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "pending",
+            OrderStatus::Confirmed => "confirmed",
+            OrderStatus::Shipped => "shipped",
+            OrderStatus::Delivered => "delivered",
+            OrderStatus::Cancelled => "cancelled",
+            OrderStatus::Returned => "returned",
         }
     }
 }
@@ -76,11 +304,280 @@ struct Order {
     product_name: String,
     quantity: i32,
     total_price: f64,
-    status: String,
+    status: OrderStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reservation_id: Option<String>,
+    // Only known for orders placed via create_validated_order; used to restock on a return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_id: Option<String>,
     #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
     created_at: DateTime<Utc>,
 }
 
+// Transactional outbox entry - written alongside an order so event publication survives a crash
+// or NATS outage; a background task polls unpublished rows and publishes them.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct OutboxEvent {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    id: Option<ObjectId>,
+    event_type: String,
+    payload: String,
+    published: bool,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    created_at: DateTime<Utc>,
+}
+
+// Write an event to the outbox instead of publishing to NATS directly, so the event survives
+// a NATS outage between the order insert and the publish. The order insert and this outbox
+// insert are two separate, non-transactional writes (this deployment's MongoDB is not a
+// replica set, so the driver's session transactions aren't available to us here) - a process
+// crash between the two still drops the event silently. This narrows that crash window to a
+// single extra write rather than eliminating it.
+async fn write_outbox_event(db: &Database, event_type: &str, payload: serde_json::Value) -> Result<(), mongodb::error::Error> {
+    let collection: Collection<OutboxEvent> = db.collection("outbox");
+    let event = OutboxEvent {
+        id: None,
+        event_type: event_type.to_string(),
+        payload: payload.to_string(),
+        published: false,
+        created_at: Utc::now(),
+    };
+    collection.insert_one(event, None).await?;
+    Ok(())
+}
+
+// Background task that polls the outbox for unpublished events and publishes them to NATS,
+// retrying with backoff and marking each event published only once NATS has acked it.
+async fn run_outbox_publisher(db: Database, nats_client: async_nats::Client, metrics: Arc<Metrics>, interval: std::time::Duration) {
+    let collection: Collection<OutboxEvent> = db.collection("outbox");
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        let pending_count = match collection.count_documents(doc! { "published": false }, None).await {
+            Ok(count) => count,
+            Err(e) => {
+                error!("Outbox publisher failed to count pending events: {}", e);
+                continue;
+            }
+        };
+        metrics.outbox_pending.set(pending_count as f64);
+
+        let mut cursor = match collection.find(doc! { "published": false }, None).await {
+            Ok(cursor) => cursor,
+            Err(e) => {
+                error!("Outbox publisher failed to query pending events: {}", e);
+                continue;
+            }
+        };
+
+        while let Some(result) = cursor.next().await {
+            let event = match result {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Outbox publisher failed to read event: {}", e);
+                    continue;
+                }
+            };
+
+            let Some(event_id) = event.id else { continue };
+
+            // `publish` only enqueues the message on the client's internal command channel; it
+            // does not confirm the server received anything. Follow it with `flush`, which
+            // round-trips to the server, so we only mark the event published once it's
+            // actually been handed off rather than on a send that could be lost if the
+            // connection drops immediately after.
+            let mut attempt = 0;
+            let published = loop {
+                let outcome = match nats_client.publish(event.event_type.clone(), event.payload.clone().into()).await {
+                    Ok(()) => nats_client.flush().await.map_err(|e| format!("flush failed: {}", e)),
+                    Err(e) => Err(format!("publish failed: {}", e)),
+                };
+                match outcome {
+                    Ok(()) => break true,
+                    Err(e) => {
+                        attempt += 1;
+                        if attempt >= 5 {
+                            error!("Outbox publisher giving up on event {} after {} attempts: {}", event_id, attempt, e);
+                            break false;
+                        }
+                        let backoff = std::time::Duration::from_millis(100 * 2u64.pow(attempt));
+                        warn!("Outbox publisher retrying event {} in {:?} (attempt {}): {}", event_id, backoff, attempt, e);
+                        tokio::time::sleep(backoff).await;
+                    }
+                }
+            };
+
+            if published {
+                if let Err(e) = collection
+                    .update_one(doc! { "_id": event_id }, doc! { "$set": { "published": true } }, None)
+                    .await
+                {
+                    error!("Outbox publisher failed to mark event {} published: {}", event_id, e);
+                } else {
+                    metrics.outbox_pending.dec();
+                }
+            }
+        }
+    }
+}
+
+// Configuration for the background low-stock monitor, read from env vars in main
+struct LowStockMonitorConfig {
+    interval: std::time::Duration,
+    window: chrono::Duration,
+    top_n: i64,
+    reorder_point: i32,
+}
+
+// Periodically aggregates recent order volume to find the top-selling products, checks their
+// current stock in inventory-service, and publishes an inventory.low_stock event for any
+// product whose stock is projected to fall below the configured reorder point.
+async fn run_low_stock_monitor(
+    db: Database,
+    http_client: reqwest::Client,
+    inventory_service_url: String,
+    nats_client: async_nats::Client,
+    metrics: Arc<Metrics>,
+    config: LowStockMonitorConfig,
+) {
+    let orders: Collection<Order> = db.collection("orders");
+    let mut ticker = tokio::time::interval(config.interval);
+
+    loop {
+        ticker.tick().await;
+
+        let window_start = BsonDateTime::from_chrono(Utc::now() - config.window);
+        let pipeline = vec![
+            doc! { "$match": { "created_at": { "$gte": window_start }, "product_id": { "$ne": mongodb::bson::Bson::Null } } },
+            doc! { "$group": { "_id": "$product_id", "product_name": { "$first": "$product_name" }, "volume": { "$sum": "$quantity" } } },
+            doc! { "$sort": { "volume": -1 } },
+            doc! { "$limit": config.top_n },
+        ];
+
+        let mut cursor = match orders.aggregate(pipeline, None).await {
+            Ok(cursor) => cursor,
+            Err(e) => {
+                error!("Low-stock monitor failed to aggregate recent orders: {}", e);
+                continue;
+            }
+        };
+
+        while let Some(result) = cursor.next().await {
+            let top_seller = match result {
+                Ok(doc) => doc,
+                Err(e) => {
+                    error!("Low-stock monitor failed to read aggregation result: {}", e);
+                    continue;
+                }
+            };
+
+            let Ok(product_id) = top_seller.get_str("_id") else { continue };
+            let product_name = top_seller.get_str("product_name").unwrap_or(product_id).to_string();
+            let volume = top_seller.get_i32("volume").unwrap_or(0);
+
+            let inventory_url = format!("{}/api/inventory/{}", inventory_service_url, product_id);
+            let request_id = RequestId(Uuid::new_v4());
+            let response = match http_client
+                .get(&inventory_url)
+                .headers(traced_headers(request_id))
+                .send()
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    error!("Low-stock monitor failed to call inventory-service for {}: {}", product_id, e);
+                    continue;
+                }
+            };
+
+            if !response.status().is_success() {
+                warn!("Low-stock monitor got status {} from inventory-service for {}", response.status(), product_id);
+                continue;
+            }
+
+            let item: InventoryItem = match response.json().await {
+                Ok(item) => item,
+                Err(e) => {
+                    error!("Low-stock monitor failed to parse inventory response for {}: {}", product_id, e);
+                    continue;
+                }
+            };
+
+            let projected_stock = item.quantity - volume;
+            if projected_stock < config.reorder_point {
+                info!(
+                    request_id = %request_id,
+                    "Projected stock for {} ({}) is {}, below reorder point {} - publishing inventory.low_stock",
+                    product_name, product_id, projected_stock, config.reorder_point
+                );
+
+                let event = serde_json::json!({
+                    "product_name": product_name,
+                    "current_qty": item.quantity,
+                    "reorder_point": config.reorder_point,
+                });
+
+                if let Err(e) = nats_client.publish("inventory.low_stock", event.to_string().into()).await {
+                    error!("Failed to publish inventory.low_stock event for {}: {}", product_name, e);
+                } else {
+                    metrics.low_stock_alerts_total.inc();
+                }
+            }
+        }
+    }
+}
+
+// Return lifecycle status
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ReturnStatus {
+    Requested,
+    Approved,
+    Rejected,
+    Refunded,
+}
+
+impl ReturnStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            ReturnStatus::Requested => "requested",
+            ReturnStatus::Approved => "approved",
+            ReturnStatus::Rejected => "rejected",
+            ReturnStatus::Refunded => "refunded",
+        }
+    }
+}
+
+// A return request against a delivered order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct Return {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    id: Option<ObjectId>,
+    order_id: ObjectId,
+    reason: String,
+    quantity: i32,
+    status: ReturnStatus,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    created_at: DateTime<Utc>,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    updated_at: DateTime<Utc>,
+}
+
+// Request body for POST /api/orders/:id/returns
+#[derive(Debug, Deserialize)]
+struct CreateReturnRequest {
+    reason: String,
+    quantity: i32,
+}
+
+// Request for inventory-service's restock endpoint
+#[derive(Debug, Serialize)]
+struct RestockInventoryRequest {
+    quantity: i32,
+}
+
 // User model from user-service
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct User {
@@ -118,6 +615,23 @@ struct InventoryItem {
     price: f64,  // Optional field with default
 }
 
+// Request body for PATCH /api/orders/:id/status
+#[derive(Debug, Deserialize)]
+struct UpdateStatusRequest {
+    status: OrderStatus,
+}
+
+// Request/response for inventory-service's reservation endpoints
+#[derive(Debug, Serialize)]
+struct ReserveInventoryRequest {
+    quantity: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReserveInventoryResponse {
+    reservation_id: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct ListQuery {
     #[serde(default)]
@@ -147,7 +661,11 @@ enum AppError {
     InternalError(String),
     HttpError(String),
     UserNotFound(i32),
-    InsufficientInventory(String, i32, i32), // product_name, requested, available
+    InvalidTransition(OrderStatus, OrderStatus), // from, to
+    ReservationFailed(String),
+    OrderNotDelivered(OrderStatus),
+    ReturnNotRequested(ReturnStatus),
+    InvalidReturnQuantity(i32, i32), // requested, ordered
 }
 
 impl IntoResponse for AppError {
@@ -170,9 +688,25 @@ impl IntoResponse for AppError {
                 error!("User not found: {}", user_id);
                 (StatusCode::BAD_REQUEST, format!("User {} not found", user_id))
             }
-            AppError::InsufficientInventory(product, requested, available) => {
-                error!("Insufficient inventory for {}: requested {}, available {}", product, requested, available);
-                (StatusCode::BAD_REQUEST, format!("Insufficient inventory for {}: requested {}, available {}", product, requested, available))
+            AppError::InvalidTransition(from, to) => {
+                error!("Invalid order status transition: {} -> {}", from.as_str(), to.as_str());
+                (StatusCode::CONFLICT, format!("Cannot transition order from {} to {}", from.as_str(), to.as_str()))
+            }
+            AppError::ReservationFailed(msg) => {
+                error!("Inventory reservation failed: {}", msg);
+                (StatusCode::BAD_REQUEST, format!("Inventory reservation failed: {}", msg))
+            }
+            AppError::OrderNotDelivered(status) => {
+                error!("Cannot request a return: order is {}, not delivered", status.as_str());
+                (StatusCode::CONFLICT, format!("Cannot request a return for an order in status {}", status.as_str()))
+            }
+            AppError::ReturnNotRequested(status) => {
+                error!("Cannot approve return: return is {}, not requested", status.as_str());
+                (StatusCode::CONFLICT, format!("Cannot approve a return in status {}", status.as_str()))
+            }
+            AppError::InvalidReturnQuantity(requested, ordered) => {
+                error!("Return quantity {} exceeds ordered quantity {}", requested, ordered);
+                (StatusCode::BAD_REQUEST, format!("Return quantity {} exceeds ordered quantity {}", requested, ordered))
             }
         };
 
@@ -234,9 +768,10 @@ async fn metrics_handler() -> impl IntoResponse {
 #[instrument(skip(state))]
 async fn create_order(
     State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
     Json(payload): Json<CreateOrderRequestSimple>,
 ) -> Result<(StatusCode, Json<Order>), AppError> {
-    info!("Creating order for user {}", payload.user_id);
+    info!(request_id = %request_id, "Creating order for user {}", payload.user_id);
 
     let collection: Collection<Order> = state.db.collection("orders");
 
@@ -246,7 +781,9 @@ async fn create_order(
         product_name: payload.product_name.clone(),
         quantity: payload.quantity,
         total_price: payload.price_per_unit * payload.quantity as f64,
-        status: "pending".to_string(),
+        status: OrderStatus::Pending,
+        reservation_id: None,
+        product_id: None,
         created_at: Utc::now(),
     };
 
@@ -256,26 +793,31 @@ async fn create_order(
     let mut created_order = order;
     created_order.id = Some(order_id);
 
-    // Publish event to NATS
-    if let Some(client) = &state.nats_client {
-        let event = serde_json::json!({
-            "event": "order.created",
-            "order_id": order_id.to_string(),
-            "user_id": payload.user_id,
-            "timestamp": Utc::now().to_rfc3339(),
-        });
-        
-        if let Err(e) = client.publish("order.created", event.to_string().into()).await {
-            error!("Failed to publish NATS event: {}", e);
-        } else {
-            info!("Published order.created event for order {}", order_id);
+    // Write the order.created event to the outbox instead of publishing directly, so it
+    // survives a NATS outage between the insert above and the publish. If the outbox write
+    // itself fails, roll back the order rather than returning success for an order that
+    // nothing will ever publish an event for. The two inserts are not transactional (see
+    // write_outbox_event), so a crash between them still drops the event silently.
+    let event = serde_json::json!({
+        "event": "order.created",
+        "order_id": order_id.to_string(),
+        "user_id": payload.user_id,
+        "request_id": request_id.to_string(),
+        "timestamp": Utc::now().to_rfc3339(),
+    });
+    if let Err(e) = write_outbox_event(&state.db, "order.created", event).await {
+        error!(request_id = %request_id, "Failed to write order.created event to outbox, rolling back order {}: {}", order_id, e);
+        if let Err(e) = collection.delete_one(doc! { "_id": order_id }, None).await {
+            error!(request_id = %request_id, "Failed to roll back order {} after outbox write failure: {}", order_id, e);
         }
+        return Err(AppError::DatabaseError(e));
     }
+    state.metrics.outbox_pending.inc();
 
     state.metrics.orders_created.inc();
     state.metrics.requests_total.inc();
-    
-    info!("Order created successfully: {}", order_id);
+
+    info!(request_id = %request_id, "Order created successfully: {}", order_id);
 
     Ok((StatusCode::CREATED, Json(created_order)))
 }
@@ -293,10 +835,106 @@ impl<'a> Injector for HeaderInjector<'a> {
     }
 }
 
+// Build HTTP headers carrying the current OpenTelemetry trace context plus the request's
+// correlation id, for service-to-service calls
+fn traced_headers(request_id: RequestId) -> reqwest::header::HeaderMap {
+    let cx = tracing::Span::current().context();
+    let mut headers = reqwest::header::HeaderMap::new();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut HeaderInjector(&mut headers));
+    });
+    if let Ok(val) = reqwest::header::HeaderValue::from_str(&request_id.to_string()) {
+        headers.insert(REQUEST_ID_HEADER, val);
+    }
+    headers
+}
+
+// Reserve inventory for an order via inventory-service (atomically checks and decrements stock server-side)
+async fn reserve_inventory(
+    state: &AppState,
+    request_id: RequestId,
+    product_id: &str,
+    quantity: i32,
+) -> Result<String, AppError> {
+    let reserve_url = format!("{}/api/inventory/{}/reserve", state.inventory_service_url, product_id);
+
+    let response = state.http_client
+        .post(&reserve_url)
+        .headers(traced_headers(request_id))
+        .json(&ReserveInventoryRequest { quantity })
+        .send()
+        .await
+        .map_err(|e| AppError::HttpError(format!("Failed to call inventory-service: {}", e)))?;
+
+    if !response.status().is_success() {
+        let detail = response.text().await.unwrap_or_default();
+        return Err(AppError::ReservationFailed(format!(
+            "inventory-service rejected reservation for product {}: {}",
+            product_id, detail
+        )));
+    }
+
+    let reservation: ReserveInventoryResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::HttpError(format!("Failed to parse reservation response: {}", e)))?;
+
+    Ok(reservation.reservation_id)
+}
+
+// Release a previously made inventory reservation (compensating action for a failed saga step)
+async fn release_reservation(state: &AppState, request_id: RequestId, reservation_id: &str) {
+    let release_url = format!(
+        "{}/api/inventory/reservations/{}/release",
+        state.inventory_service_url, reservation_id
+    );
+
+    let result = state.http_client
+        .post(&release_url)
+        .headers(traced_headers(request_id))
+        .send()
+        .await;
+
+    match result {
+        Ok(response) if response.status().is_success() => {
+            info!("Released inventory reservation {}", reservation_id);
+        }
+        Ok(response) => {
+            error!("Failed to release reservation {}: status {}", reservation_id, response.status());
+        }
+        Err(e) => {
+            error!("Failed to release reservation {}: {}", reservation_id, e);
+        }
+    }
+}
+
+// Restock inventory for a returned quantity
+async fn restock_inventory(state: &AppState, request_id: RequestId, product_id: &str, quantity: i32) -> Result<(), AppError> {
+    let restock_url = format!("{}/api/inventory/{}/restock", state.inventory_service_url, product_id);
+
+    let response = state.http_client
+        .post(&restock_url)
+        .headers(traced_headers(request_id))
+        .json(&RestockInventoryRequest { quantity })
+        .send()
+        .await
+        .map_err(|e| AppError::HttpError(format!("Failed to call inventory-service: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::HttpError(format!(
+            "inventory-service rejected restock for product {}: status {}",
+            product_id, response.status()
+        )));
+    }
+
+    Ok(())
+}
+
 // Create validated order - calls user-service and inventory-service to verify and get details
 #[instrument(skip(state))]
 async fn create_validated_order(
     State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
     Json(payload): Json<CreateOrderRequest>,
 ) -> Result<(StatusCode, Json<Order>), AppError> {
     let trace_id = get_trace_id();
@@ -305,17 +943,10 @@ async fn create_validated_order(
     // Step 1: Validate that the user exists by calling user-service
     info!(trace_id = %trace_id, "Step 1: Validating user {} exists via user-service", payload.user_id);
     let user_url = format!("{}/api/users/{}", state.user_service_url, payload.user_id);
-    
-    // Inject trace context into HTTP headers
-    let cx = tracing::Span::current().context();
-    let mut headers = reqwest::header::HeaderMap::new();
-    global::get_text_map_propagator(|propagator| {
-        propagator.inject_context(&cx, &mut HeaderInjector(&mut headers));
-    });
-    
+
     let user_response = state.http_client
         .get(&user_url)
-        .headers(headers)
+        .headers(traced_headers(request_id))
         .send()
         .await
         .map_err(|e| AppError::HttpError(format!("Failed to call user-service: {}", e)))?;
@@ -335,17 +966,10 @@ async fn create_validated_order(
     // Step 2: Get product details from inventory-service
     info!(trace_id = %trace_id, "Step 2: Fetching product {} from inventory-service", payload.product_id);
     let inventory_url = format!("{}/api/inventory/{}", state.inventory_service_url, payload.product_id);
-    
-    // Inject trace context into HTTP headers
-    let cx = tracing::Span::current().context();
-    let mut headers = reqwest::header::HeaderMap::new();
-    global::get_text_map_propagator(|propagator| {
-        propagator.inject_context(&cx, &mut HeaderInjector(&mut headers));
-    });
-    
+
     let inventory_response = state.http_client
         .get(&inventory_url)
-        .headers(headers)
+        .headers(traced_headers(request_id))
         .send()
         .await
         .map_err(|e| AppError::HttpError(format!("Failed to call inventory-service: {}", e)))?;
@@ -365,21 +989,13 @@ async fn create_validated_order(
     
     info!(trace_id = %trace_id, "Product found: {} - ${} (stock: {})", inventory_item.product_name, price, inventory_item.quantity);
 
-    // Check if sufficient quantity is available
-    if inventory_item.quantity < payload.quantity {
-        error!(trace_id = %trace_id, "Insufficient inventory for {}: requested {}, available {}", 
-               inventory_item.product_name, payload.quantity, inventory_item.quantity);
-        return Err(AppError::HttpError(format!(
-            "Insufficient inventory: requested {}, available {}", 
-            payload.quantity, 
-            inventory_item.quantity
-        )));
-    }
-    
-    info!(trace_id = %trace_id, "Inventory validated: {} available (requested {})", inventory_item.quantity, payload.quantity);
+    // Step 3: Reserve the stock in inventory-service (atomic decrement + availability check happens there)
+    info!(trace_id = %trace_id, "Step 3: Reserving {} x {} via inventory-service", payload.quantity, inventory_item.product_name);
+    let reservation_id = reserve_inventory(&state, request_id, &payload.product_id, payload.quantity).await?;
+    info!(trace_id = %trace_id, "Reservation {} created for product {}", reservation_id, payload.product_id);
 
-    // Step 3: Create the order
-    info!(trace_id = %trace_id, "Step 3: Creating order in database");
+    // Step 4: Create the order, compensating the reservation if the insert fails
+    info!(trace_id = %trace_id, "Step 4: Creating order in database");
     let collection: Collection<Order> = state.db.collection("orders");
 
     let total_price = price * payload.quantity as f64;
@@ -389,41 +1005,59 @@ async fn create_validated_order(
         product_name: inventory_item.product_name.clone(),
         quantity: payload.quantity,
         total_price,
-        status: "pending".to_string(),
+        status: OrderStatus::Pending,
+        reservation_id: Some(reservation_id.clone()),
+        product_id: Some(payload.product_id.clone()),
         created_at: Utc::now(),
     };
 
-    let result = collection.insert_one(order.clone(), None).await?;
+    let result = match collection.insert_one(order.clone(), None).await {
+        Ok(result) => result,
+        Err(e) => {
+            error!(trace_id = %trace_id, "Order insert failed, releasing reservation {}: {}", reservation_id, e);
+            release_reservation(&state, request_id, &reservation_id).await;
+            return Err(AppError::DatabaseError(e));
+        }
+    };
     let order_id = result.inserted_id.as_object_id().unwrap();
 
     let mut created_order = order;
     created_order.id = Some(order_id);
 
-    // Publish event to NATS
-    if let Some(client) = &state.nats_client {
-        let event = serde_json::json!({
-            "event": "order.created",
-            "order_id": order_id.to_string(),
-            "user_id": payload.user_id,
-            "user_name": user.name,
-            "product_id": payload.product_id,
-            "product_name": inventory_item.product_name,
-            "quantity": payload.quantity,
-            "total_price": total_price,
-            "timestamp": Utc::now().to_rfc3339(),
-        });
-        
-        if let Err(e) = client.publish("order.created", event.to_string().into()).await {
-            error!("Failed to publish NATS event: {}", e);
-        } else {
-            info!("Published order.created event for order {}", order_id);
+    // Write the order.created event to the outbox instead of publishing directly, so it
+    // survives a NATS outage between the insert above and the publish. The two inserts are
+    // not transactional (see write_outbox_event), so a crash between them still drops the
+    // event silently.
+    let event = serde_json::json!({
+        "event": "order.created",
+        "order_id": order_id.to_string(),
+        "user_id": payload.user_id,
+        "user_name": user.name,
+        "product_id": payload.product_id,
+        "product_name": inventory_item.product_name,
+        "quantity": payload.quantity,
+        "total_price": total_price,
+        "request_id": request_id.to_string(),
+        "timestamp": Utc::now().to_rfc3339(),
+    });
+    if let Err(e) = write_outbox_event(&state.db, "order.created", event).await {
+        error!(
+            trace_id = %trace_id,
+            "Failed to write order.created event to outbox, rolling back order {} and releasing reservation {}: {}",
+            order_id, reservation_id, e
+        );
+        if let Err(e) = collection.delete_one(doc! { "_id": order_id }, None).await {
+            error!(trace_id = %trace_id, "Failed to roll back order {} after outbox write failure: {}", order_id, e);
         }
+        release_reservation(&state, request_id, &reservation_id).await;
+        return Err(AppError::DatabaseError(e));
     }
+    state.metrics.outbox_pending.inc();
 
     state.metrics.orders_created.inc();
     state.metrics.requests_total.inc();
-    
-    info!(trace_id = %trace_id, "✅ Validated order created: {} for user {} ({}), product: {} x{}", 
+
+    info!(trace_id = %trace_id, "✅ Validated order created: {} for user {} ({}), product: {} x{}",
           order_id, user.name, user.email, inventory_item.product_name, payload.quantity);
 
     Ok((StatusCode::CREATED, Json(created_order)))
@@ -487,6 +1121,273 @@ async fn get_order(
     Ok(Json(order))
 }
 
+// Update order status handler
+#[instrument(skip(state))]
+async fn update_order_status(
+    State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateStatusRequest>,
+) -> Result<Json<Order>, AppError> {
+    let trace_id = get_trace_id();
+    info!(request_id = %request_id, trace_id = %trace_id, "Updating order {} status to {}", id, payload.status.as_str());
+
+    let object_id = ObjectId::parse_str(&id)
+        .map_err(|e| AppError::InternalError(format!("Invalid ID: {}", e)))?;
+
+    let collection: Collection<Order> = state.db.collection("orders");
+    let order = collection
+        .find_one(doc! { "_id": object_id }, None)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !order.status.can_transition_to(payload.status) {
+        return Err(AppError::InvalidTransition(order.status, payload.status));
+    }
+
+    // Guard the write with the status we validated the transition against, so two concurrent
+    // requests that both read the same pre-transition status can't both pass can_transition_to
+    // and both write - the one that loses the race gets a conflict instead of silently
+    // bypassing the transition table.
+    let updated_order = collection
+        .find_one_and_update(
+            doc! { "_id": object_id, "status": order.status.as_str() },
+            doc! { "$set": { "status": payload.status.as_str() } },
+            mongodb::options::FindOneAndUpdateOptions::builder()
+                .return_document(mongodb::options::ReturnDocument::After)
+                .build(),
+        )
+        .await?
+        .ok_or_else(|| AppError::InvalidTransition(order.status, payload.status))?;
+
+    // Cancelling a reserved order must give the stock back, or the reservation leaks forever.
+    if payload.status == OrderStatus::Cancelled {
+        if let Some(reservation_id) = &order.reservation_id {
+            release_reservation(&state, request_id, reservation_id).await;
+        }
+    }
+
+    // Publish event to NATS
+    if let Some(client) = &state.nats_client {
+        let event = serde_json::json!({
+            "event": "order.status_changed",
+            "order_id": id,
+            "old_status": order.status.as_str(),
+            "new_status": payload.status.as_str(),
+            "trace_id": trace_id,
+            "request_id": request_id.to_string(),
+            "timestamp": Utc::now().to_rfc3339(),
+        });
+
+        if let Err(e) = client.publish("order.status_changed", event.to_string().into()).await {
+            error!("Failed to publish NATS event: {}", e);
+        } else {
+            info!("Published order.status_changed event for order {}", id);
+        }
+    }
+
+    state.metrics.orders_status_changed.with_label_values(&[payload.status.as_str()]).inc();
+    state.metrics.requests_total.inc();
+
+    info!(trace_id = %trace_id, "Order {} status changed: {} -> {}", id, order.status.as_str(), payload.status.as_str());
+
+    Ok(Json(updated_order))
+}
+
+// Request a return for a delivered order
+#[instrument(skip(state))]
+async fn create_return(
+    State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
+    Path(order_id): Path<String>,
+    Json(payload): Json<CreateReturnRequest>,
+) -> Result<(StatusCode, Json<Return>), AppError> {
+    info!(request_id = %request_id, "Requesting return for order {}", order_id);
+
+    let object_id = ObjectId::parse_str(&order_id)
+        .map_err(|e| AppError::InternalError(format!("Invalid ID: {}", e)))?;
+
+    let orders: Collection<Order> = state.db.collection("orders");
+    let order = orders
+        .find_one(doc! { "_id": object_id }, None)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if order.status != OrderStatus::Delivered {
+        return Err(AppError::OrderNotDelivered(order.status));
+    }
+
+    if payload.quantity > order.quantity {
+        return Err(AppError::InvalidReturnQuantity(payload.quantity, order.quantity));
+    }
+
+    let now = Utc::now();
+    let new_return = Return {
+        id: None,
+        order_id: object_id,
+        reason: payload.reason,
+        quantity: payload.quantity,
+        status: ReturnStatus::Requested,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let returns: Collection<Return> = state.db.collection("returns");
+    let result = returns.insert_one(new_return.clone(), None).await?;
+    let return_id = result.inserted_id.as_object_id().unwrap();
+
+    let mut created_return = new_return;
+    created_return.id = Some(return_id);
+
+    state.metrics.returns_created.inc();
+    state.metrics.requests_total.inc();
+
+    info!(request_id = %request_id, "Return {} requested for order {}", return_id, order_id);
+
+    Ok((StatusCode::CREATED, Json(created_return)))
+}
+
+// List returns filed against an order
+#[instrument(skip(state))]
+async fn list_returns_for_order(
+    State(state): State<AppState>,
+    Path(order_id): Path<String>,
+) -> Result<Json<Vec<Return>>, AppError> {
+    info!("Listing returns for order {}", order_id);
+
+    let object_id = ObjectId::parse_str(&order_id)
+        .map_err(|e| AppError::InternalError(format!("Invalid ID: {}", e)))?;
+
+    let returns: Collection<Return> = state.db.collection("returns");
+    let mut cursor = returns.find(doc! { "order_id": object_id }, None).await?;
+    let mut results = Vec::new();
+
+    while let Some(result) = cursor.next().await {
+        match result {
+            Ok(r) => results.push(r),
+            Err(e) => error!("Error reading return: {}", e),
+        }
+    }
+
+    state.metrics.requests_total.inc();
+
+    Ok(Json(results))
+}
+
+// Approve a return: transitions the order to Returned, restocks inventory, and emits order.returned
+#[instrument(skip(state))]
+async fn approve_return(
+    State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
+    Path(id): Path<String>,
+) -> Result<Json<Return>, AppError> {
+    info!(request_id = %request_id, "Approving return {}", id);
+
+    let object_id = ObjectId::parse_str(&id)
+        .map_err(|e| AppError::InternalError(format!("Invalid ID: {}", e)))?;
+
+    let returns: Collection<Return> = state.db.collection("returns");
+    let ret = returns
+        .find_one(doc! { "_id": object_id }, None)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if ret.status != ReturnStatus::Requested {
+        return Err(AppError::ReturnNotRequested(ret.status));
+    }
+
+    let orders: Collection<Order> = state.db.collection("orders");
+    let order = orders
+        .find_one(doc! { "_id": ret.order_id }, None)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    if !order.status.can_transition_to(OrderStatus::Returned) {
+        return Err(AppError::InvalidTransition(order.status, OrderStatus::Returned));
+    }
+
+    // Claim the return first with an atomic CAS before restocking, so if two approval requests
+    // race each other only the one that wins this write ever issues the compensating restock
+    // call - the loser sees its status is no longer Requested and bails out instead of
+    // double-crediting the stock.
+    let claimed = returns
+        .find_one_and_update(
+            doc! { "_id": object_id, "status": ReturnStatus::Requested.as_str() },
+            doc! { "$set": { "status": ReturnStatus::Approved.as_str() } },
+            None,
+        )
+        .await?;
+    if claimed.is_none() {
+        let current = returns
+            .find_one(doc! { "_id": object_id }, None)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        return Err(AppError::ReturnNotRequested(current.status));
+    }
+
+    // Restock before marking anything settled: if the compensating call fails, the request
+    // should fail too rather than tell the customer they were refunded while the stock was
+    // never actually given back.
+    match &order.product_id {
+        Some(product_id) => {
+            restock_inventory(&state, request_id, product_id, ret.quantity).await?;
+        }
+        None => {
+            warn!(request_id = %request_id, "Order {} has no product id on record, skipping inventory restock", ret.order_id);
+        }
+    }
+
+    // Guard both writes with the statuses we validated above, so a concurrent status change
+    // racing this one can't both pass validation and both write - the loser gets a conflict
+    // instead of silently bypassing the transition/return-state checks.
+    orders
+        .find_one_and_update(
+            doc! { "_id": ret.order_id, "status": order.status.as_str() },
+            doc! { "$set": { "status": OrderStatus::Returned.as_str() } },
+            None,
+        )
+        .await?
+        .ok_or_else(|| AppError::InvalidTransition(order.status, OrderStatus::Returned))?;
+
+    let now = Utc::now();
+    let updated_return = returns
+        .find_one_and_update(
+            doc! { "_id": object_id, "status": ReturnStatus::Approved.as_str() },
+            doc! { "$set": { "status": ReturnStatus::Refunded.as_str(), "updated_at": BsonDateTime::from_chrono(now) } },
+            mongodb::options::FindOneAndUpdateOptions::builder()
+                .return_document(mongodb::options::ReturnDocument::After)
+                .build(),
+        )
+        .await?
+        .ok_or(AppError::ReturnNotRequested(ret.status))?;
+
+    // Publish event to NATS
+    if let Some(client) = &state.nats_client {
+        let event = serde_json::json!({
+            "event": "order.returned",
+            "order_id": ret.order_id.to_string(),
+            "return_id": object_id.to_string(),
+            "quantity": ret.quantity,
+            "reason": ret.reason,
+            "request_id": request_id.to_string(),
+            "timestamp": now.to_rfc3339(),
+        });
+
+        if let Err(e) = client.publish("order.returned", event.to_string().into()).await {
+            error!("Failed to publish NATS event: {}", e);
+        } else {
+            info!("Published order.returned event for order {}", ret.order_id);
+        }
+    }
+
+    state.metrics.returns_refunded.inc();
+    state.metrics.requests_total.inc();
+
+    info!(request_id = %request_id, "Return {} approved and order {} marked returned", object_id, ret.order_id);
+
+    Ok(Json(updated_return))
+}
+
 // Initialize tracing
 fn init_tracing() {
     let otlp_endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
@@ -589,6 +1490,63 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or_else(|_| "http://go-inventory-service:8002".to_string());
     info!("Inventory service URL configured: {}", inventory_service_url);
 
+    // Spawn the outbox publisher task so events written by the handlers get delivered to NATS
+    if let Some(client) = &nats_client {
+        let outbox_interval_secs = std::env::var("OUTBOX_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2);
+        info!("Starting outbox publisher with {}s poll interval", outbox_interval_secs);
+        tokio::spawn(run_outbox_publisher(
+            db.clone(),
+            client.clone(),
+            metrics.clone(),
+            std::time::Duration::from_secs(outbox_interval_secs),
+        ));
+    } else {
+        warn!("NATS client not initialized, outbox publisher not started");
+    }
+
+    // Spawn the low-stock monitor so sustained demand on top-selling products triggers a
+    // reorder notification before inventory-service actually runs out
+    if let Some(client) = &nats_client {
+        let low_stock_interval_secs = std::env::var("LOW_STOCK_CHECK_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+        let low_stock_window_mins = std::env::var("LOW_STOCK_WINDOW_MINS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+        let low_stock_top_n = std::env::var("LOW_STOCK_TOP_N")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let low_stock_reorder_point = std::env::var("LOW_STOCK_REORDER_POINT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10);
+        info!(
+            "Starting low-stock monitor: interval={}s window={}m top_n={} reorder_point={}",
+            low_stock_interval_secs, low_stock_window_mins, low_stock_top_n, low_stock_reorder_point
+        );
+        tokio::spawn(run_low_stock_monitor(
+            db.clone(),
+            http_client.clone(),
+            inventory_service_url.clone(),
+            client.clone(),
+            metrics.clone(),
+            LowStockMonitorConfig {
+                interval: std::time::Duration::from_secs(low_stock_interval_secs),
+                window: chrono::Duration::minutes(low_stock_window_mins),
+                top_n: low_stock_top_n,
+                reorder_point: low_stock_reorder_point,
+            },
+        ));
+    } else {
+        warn!("NATS client not initialized, low-stock monitor not started");
+    }
+
     // Create application state
     let state = AppState {
         db,
@@ -599,6 +1557,35 @@ async fn main() -> anyhow::Result<()> {
         inventory_service_url,
     };
 
+    // Build the CORS layer from a comma-separated list of allowed origins; falls back to
+    // allowing any origin so the demo keeps working without extra configuration.
+    let cors_allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS").ok();
+    let cors_layer = match cors_allowed_origins.as_deref() {
+        Some(origins) if !origins.trim().is_empty() && origins.trim() != "*" => {
+            let parsed: Vec<HeaderValue> = origins
+                .split(',')
+                .filter_map(|o| HeaderValue::from_str(o.trim()).ok())
+                .collect();
+            info!("CORS allowed origins: {}", origins);
+            CorsLayer::new()
+                .allow_origin(parsed)
+                .allow_methods(Any)
+                .allow_headers(Any)
+        }
+        _ => {
+            info!("CORS_ALLOWED_ORIGINS not set (or \"*\"), allowing any origin");
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any)
+        }
+    };
+
+    let compression_enabled = std::env::var("ENABLE_COMPRESSION")
+        .map(|v| v != "false" && v != "0")
+        .unwrap_or(true);
+    info!("Response compression enabled: {}", compression_enabled);
+
     // Build router
     let app = Router::new()
         .route("/health", get(health_check))
@@ -606,15 +1593,39 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/orders", post(create_order).get(list_orders))
         .route("/api/orders/validated", post(create_validated_order))
         .route("/api/orders/:id", get(get_order))
+        .route("/api/orders/:id/status", patch(update_order_status))
+        .route("/api/orders/:id/returns", post(create_return).get(list_returns_for_order))
+        .route("/api/returns/:id/approve", post(approve_return))
+        // Each successive .layer() call becomes the new outermost layer, so SetSensitiveHeadersLayer
+        // is added after TraceLayer here to wrap it - marking auth/cookie headers sensitive before
+        // TraceLayer (or anything it wraps) ever gets to log them. The layers added below sit
+        // outside SetSensitiveHeadersLayer and see requests before the marking.
         .layer(TraceLayer::new_for_http())
+        .layer(SetSensitiveHeadersLayer::new([header::AUTHORIZATION, header::COOKIE]))
+        // PropagateHeaderLayer reads the x-request-id request header to echo onto the response,
+        // so RequestIdLayer (which stamps that header when the client didn't send one) must wrap
+        // it - added after, so it's the outer of the two and runs first on the way in.
+        .layer(PropagateHeaderLayer::new(HeaderName::from_static(REQUEST_ID_HEADER)))
+        .layer(RequestIdLayer)
+        .layer(cors_layer)
         .with_state(state);
 
+    let app = if compression_enabled {
+        app.layer(CompressionLayer::new())
+    } else {
+        app
+    };
+
     // Start server
     let addr = SocketAddr::from(([0, 0, 0, 0], 8001));
     info!("Order service listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }